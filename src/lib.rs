@@ -1,6 +1,8 @@
-use std::sync::{Mutex, Arc};
+use std::sync::{Mutex, RwLock, Arc};
 use std::time::{Instant, Duration};
 
+pub mod utils;
+
 
 /// Shortcut for timing a section of code and adding it to the given ['OneList<OneDuration>']
 #[macro_export]
@@ -87,7 +89,125 @@ impl<T> OneList<T> where T : Send + Sync + Clone {
     /// use this only as needed
     pub fn list_copy(&self) -> Vec<T> {
         let items = self.items.lock().unwrap();
-        items.iter().map(|p| p.clone()).collect()
+        items.iter().cloned().collect()
+    }
+}
+
+impl<T> Default for OneList<T> where T : Send + Sync + Clone {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a chain source must be present. Imports Arti's required-vs-optional distinction: a
+/// `MustRead` source that is absent (or fails to parse) is an error, while a `TolerateAbsence` source
+/// may be missing. A parse error on a *present* file is always an error either way. Used by the
+/// `read_chain_checked_toml` reader generated by [`create_config!`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MustRead {
+    MustRead,
+    TolerateAbsence,
+}
+
+/// Errors from the checked / multi-format chain readers generated by [`create_config!`]. Carries the
+/// offending path so applications can fail fast with a useful message when an explicitly-specified
+/// config file is unreadable.
+#[derive(Debug)]
+pub enum ConfigError {
+    FileNotFound(String),
+    TomlParse(String, toml::de::Error),
+    /// Format-agnostic parse failure (path + rendered message) used by the multi-format reader.
+    Parse(String, String),
+    /// A chain entry whose extension isn't a supported config format.
+    UnsupportedFormat(String),
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::FileNotFound(path) => write!(f, "required config file not found: {}", path),
+            ConfigError::TomlParse(path, error) => write!(f, "failed to parse config file {}: {}", path, error),
+            ConfigError::Parse(path, error) => write!(f, "failed to parse config file {}: {}", path, error),
+            ConfigError::UnsupportedFormat(path) => write!(f, "unsupported config file format: {}", path),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// A threadsafe, hot-reloadable handle to a loaded configuration. The value lives in an
+/// [`Arc`]` + `[`RwLock`] shared with the reload thread. Build one with [`Self::watch_chain`] to
+/// keep the value in sync with the config files on disk; readers call [`Self::get`] for a cheap clone of
+/// the current value. Unlike [`OneList`], the handle itself does not implement `Clone`. Dropping the
+/// handle stops watching.
+pub struct OneConfig<T> where T : Send + Sync + Clone {
+    inner: Arc<RwLock<T>>,
+    //Keeping the watcher alive is what keeps events flowing; dropping it stops watching and, once the
+    //event channel closes, lets the reload thread exit on its own. Only present with the `watch` feature.
+    #[cfg(feature = "watch")]
+    _watcher: Option<notify::RecommendedWatcher>,
+}
+
+impl<T> OneConfig<T> where T : Send + Sync + Clone + Default + 'static {
+    /// Wrap an already-loaded value without watching anything.
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(value)),
+            #[cfg(feature = "watch")]
+            _watcher: None,
+        }
+    }
+
+    /// Get a cheap clone of the current config value.
+    pub fn get(&self) -> T {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// Load `chain` with `loader`, then watch every file in it and reload on change. `loader` returns
+    /// `None` when it can't produce a usable value (e.g. a parse error); on reload that keeps the
+    /// previous value rather than clobbering good config with a failure. Watching begins *before* the
+    /// initial read so a change landing during startup is not missed, and a burst of events is debounced
+    /// so a half-written file doesn't trigger a reload mid-write.
+    ///
+    /// Requires the `watch` cargo feature (pulls in the `notify` filesystem watcher).
+    #[cfg(feature = "watch")]
+    pub fn watch_chain<F>(chain: Vec<String>, loader: F) -> notify::Result<Self>
+        where F : Fn(&[String]) -> Option<T> + Send + 'static
+    {
+        use notify::{Watcher, RecursiveMode};
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            //If the receiver is gone we're shutting down; nothing to do
+            let _ = tx.send(res);
+        })?;
+
+        //Begin watching BEFORE the initial read. A path that doesn't exist yet can't be watched, which
+        //is fine (the silent-skip story), so ignore those errors.
+        for path in &chain {
+            let _ = watcher.watch(std::path::Path::new(path), RecursiveMode::NonRecursive);
+        }
+
+        let initial = loader(&chain).unwrap_or_else(|| {
+            println!("OneConfig initial load failed; starting from defaults");
+            T::default()
+        });
+        let inner = Arc::new(RwLock::new(initial));
+
+        let reload_inner = Arc::clone(&inner);
+        let reload_chain = chain.clone();
+        std::thread::spawn(move || {
+            while rx.recv().is_ok() {
+                //Debounce: drain the rest of the burst so we reload once the writes settle
+                while rx.recv_timeout(Duration::from_millis(200)).is_ok() {}
+                match loader(&reload_chain) {
+                    Some(value) => { *reload_inner.write().unwrap() = value; }
+                    None => println!("OneConfig reload failed; keeping previous config"),
+                }
+            }
+        });
+
+        Ok(Self { inner, _watcher: Some(watcher) })
     }
 }
 
@@ -107,10 +227,19 @@ mod tests {
 
         assert_eq!(vec1.len(), vec2.len());
         assert_eq!(vec1.len(), 2);
-        assert_eq!(vec1.get(0), vec2.get(0));
+        assert_eq!(vec1.first(), vec2.first());
         assert_eq!(vec1.get(1), vec2.get(1));
     }
 
+    #[test]
+    fn oneconfig_get_clones() {
+        let config = OneConfig::new(String::from("hello"));
+        let other = config.get();
+        assert_eq!(other, String::from("hello"));
+        //get() hands back an independent clone of the current value
+        assert_eq!(config.get(), other);
+    }
+
     #[test]
     fn macro_works() {
         let mut list = OneList::<OneDuration>::new();
@@ -121,7 +250,7 @@ mod tests {
         assert_eq!(count, 1);
         let durs = list.list_copy();
         assert_eq!(1, durs.len());
-        assert_eq!("wow", &durs.get(0).unwrap().name);
+        assert_eq!("wow", &durs.first().unwrap().name);
     }
 
 }
@@ -26,9 +26,69 @@ macro_rules! create_config {
 
         impl $configname {
             //Mutate ourselves to apply the non-empty fields from the optional config. This has to be in the macro...
-            fn apply_optional(&mut self, opt: $optconfigname) {
+            //When `track` is set, record into `sources` which file supplied each overwritten field (last
+            //writer wins, matching value precedence). Provenance is kept out of the config struct itself so
+            //the common, non-tracking case keeps a stable, literal-constructible shape; the opt-in tracked
+            //readers thread a map through and hand it back to the caller.
+            fn apply_optional(&mut self, opt: $optconfigname, source: &str, sources: &mut std::collections::HashMap<&'static str, String>, track: bool) {
                 $(
-                    if let Some(item) = opt.$name { self.$name = item; }
+                    if let Some(item) = opt.$name {
+                        self.$name = item;
+                        if track {
+                            sources.insert(stringify!($name), source.to_string());
+                        }
+                    }
+                )*
+            }
+            //Overlay values pulled from process environment variables on top of whatever is already set.
+            //Like Cargo's config system, each field maps to an env var: field `some_int` with prefix `MYAPP`
+            //is looked up as `MYAPP_SOME_INT` (field name uppercased, dashes turned into underscores). This
+            //has to be in the macro for the same reason apply_optional does: it needs the per-field names/types.
+            fn apply_environment(&mut self, env_prefix: &str, sources: &mut std::collections::HashMap<&'static str, String>, track: bool) {
+                //An env value is always a string, but the target field could be a string, a number, a bool
+                //or a sequence. Rather than guess the type up front (which mis-coerced e.g. a numeric-looking
+                //String field), produce candidate toml::Values in preference order and let the field's own
+                //serde deserialization pick the first that fits. String-first means a String field keeps its
+                //literal value (commas included), and the comma-split array candidates are only ever accepted
+                //by sequence-typed fields.
+                fn env_scalar(raw: &str) -> toml::Value {
+                    if let Ok(i) = raw.parse::<i64>() { toml::Value::Integer(i) }
+                    else if let Ok(b) = raw.parse::<bool>() { toml::Value::Boolean(b) }
+                    else if let Ok(f) = raw.parse::<f64>() { toml::Value::Float(f) }
+                    else { toml::Value::String(raw.to_string()) }
+                }
+                fn env_candidates(raw: &str) -> Vec<toml::Value> {
+                    let mut candidates = vec![toml::Value::String(raw.to_string())];
+                    if let Ok(i) = raw.parse::<i64>() { candidates.push(toml::Value::Integer(i)); }
+                    if let Ok(b) = raw.parse::<bool>() { candidates.push(toml::Value::Boolean(b)); }
+                    if let Ok(f) = raw.parse::<f64>() { candidates.push(toml::Value::Float(f)); }
+                    //Sequence candidates: comma-split, tried only after the scalar ones so a scalar field with
+                    //a comma in it is never turned into an array.
+                    if raw.contains(',') {
+                        let parts: Vec<&str> = raw.split(',').map(|s| s.trim()).collect();
+                        candidates.push(toml::Value::Array(parts.iter().map(|s| toml::Value::String(s.to_string())).collect()));
+                        candidates.push(toml::Value::Array(parts.iter().map(|s| env_scalar(s)).collect()));
+                    }
+                    candidates
+                }
+                $(
+                    let var_name = format!("{}_{}", env_prefix, stringify!($name).to_uppercase().replace('-', "_"));
+                    if let Ok(raw) = std::env::var(&var_name) {
+                        let mut applied = false;
+                        for candidate in env_candidates(&raw) {
+                            if let Ok(parsed) = <$type as serde::Deserialize>::deserialize(candidate) {
+                                self.$name = parsed;
+                                if track {
+                                    sources.insert(stringify!($name), var_name.clone());
+                                }
+                                applied = true;
+                                break;
+                            }
+                        }
+                        if !applied {
+                            println!("read_chain_with_env_toml: could not parse env var {} into field {}", var_name, stringify!($name));
+                        }
+                    }
                 )*
             }
             //* Even though a trait would be better for these next two functions, it's just WAY easier to put them
@@ -38,47 +98,267 @@ macro_rules! create_config {
             /// another in the order given. It starts with purely default values. It does not throw errors on 
             /// files not existing
             pub fn read_chain_toml(chain: Vec<String>) -> Self {
-                let mut result = Self::default(); 
-
-                for filename in chain {
-                    //Maybe async someday? idk. Also reading into memory? It's just configs so it's fine
-                    //but clearly there are much better ways (serde gives from_reader)
-                    let data = std::fs::read_to_string(filename);
-                    match data {
-                        Ok(data) => {
-                            let config_result: Result<$optconfigname, _> = toml::from_str(&data);
-                            match config_result {
-                                Ok(config) => {
-                                    result.apply_optional(config);
+                Self::read_chain_toml_tracked(chain, false).0
+            }
+            /// Same as [`Self::read_chain_toml`], but also returns per-field provenance: a map from field
+            /// name to the filename (chain step) that last set it. Provenance is returned separately rather
+            /// than stored on the config so the plain config keeps a stable, literal-constructible shape.
+            /// Opt in to this only when you need to debug layered overrides.
+            pub fn read_chain_tracked_toml(chain: Vec<String>) -> (Self, std::collections::HashMap<&'static str, String>) {
+                Self::read_chain_toml_tracked(chain, true)
+            }
+            fn read_chain_toml_tracked(chain: Vec<String>, track: bool) -> (Self, std::collections::HashMap<&'static str, String>) {
+                let mut result = Self::default();
+                let mut sources = std::collections::HashMap::new();
+
+                for entry in chain {
+                    //A chain entry can name a directory or a file with a sibling `.d` directory; expand it
+                    //into the actual ordered list of toml files before applying each one.
+                    for filename in Self::expand_chain_entry(&entry) {
+                        result.apply_toml_file(&filename, &mut sources, track);
+                    }
+                }
+
+                (result, sources)
+            }
+            /// Like [`Self::read_chain_toml`], but each source is tagged with whether it must be read or may
+            /// tolerate absence (see [`$crate::MustRead`]). A required source that is absent, and *any*
+            /// present source that fails to parse, return a [`$crate::ConfigError`]; an optional source that
+            /// is merely absent is skipped. Use this when booting with silent defaults would hide an
+            /// unreadable, explicitly-named config.
+            pub fn read_chain_checked_toml(chain: Vec<(String, $crate::MustRead)>) -> Result<Self, $crate::ConfigError> {
+                let mut result = Self::default();
+                //This reader doesn't track provenance; a throwaway map keeps apply_optional's signature uniform
+                let mut sources = std::collections::HashMap::new();
+
+                for (entry, must_read) in chain {
+                    let filenames = Self::expand_chain_entry(&entry);
+                    //A required entry that expands to nothing (e.g. an existing but empty directory, or a
+                    //`.d` that contributes no fragments) would otherwise be silently satisfied: the inner
+                    //loop never runs, so the missing-file check below never fires. Treat that as not-found.
+                    if filenames.is_empty() && must_read == $crate::MustRead::MustRead {
+                        return Err($crate::ConfigError::FileNotFound(entry));
+                    }
+                    for filename in filenames {
+                        match std::fs::read_to_string(&filename) {
+                            Ok(data) => {
+                                match toml::from_str::<$optconfigname>(&data) {
+                                    Ok(config) => { result.apply_optional(config, &filename, &mut sources, false); }
+                                    //A parse error on a present file is always surfaced, required or not
+                                    Err(error) => { return Err($crate::ConfigError::TomlParse(filename, error)); }
                                 }
-                                Err(error) => {
-                                    println!("read_chain_json json parse error: {}", error.to_string())
+                            }
+                            Err(_) => {
+                                if must_read == $crate::MustRead::MustRead {
+                                    return Err($crate::ConfigError::FileNotFound(filename));
                                 }
+                                //TolerateAbsence: a missing optional source is fine, just skip it
                             }
                         }
+                    }
+                }
+
+                Ok(result)
+            }
+            /// Convenience over [`Self::read_chain_toml`] for the case where configuration lives entirely in
+            /// a directory of fragment files: reads every `*.toml` in `dir` (lexicographic order) as an
+            /// overlay. A missing directory is silently ignored, like any other absent chain source.
+            pub fn read_chain_dir_toml(dir: &str) -> Self {
+                Self::read_chain_toml(vec![dir.to_string()])
+            }
+            /// Like [`Self::read_chain_toml`], but each file's format is chosen from its extension so a
+            /// single chain can mix `.toml`, `.json` and `.yaml`/`.yml` sources (e.g. `settings.toml`
+            /// overridden by `settings.Prod.json`). The optional config already derives
+            /// [`serde::Deserialize`], so this just picks the right parser per file and keeps the same
+            /// overlay semantics. Missing files are skipped; a parse error or an unknown extension is an
+            /// error rather than being silently ignored.
+            ///
+            /// JSON support requires a `json` cargo feature and YAML a `yaml` feature; without them a
+            /// `.json`/`.yaml` entry is treated as an unsupported format. These feature checks are part of
+            /// the macro's output, so they are evaluated in the crate that invokes `create_config!`: that
+            /// crate needs the feature of that name and `serde_json`/`serde_yaml` as its own dependency.
+            pub fn read_chain_auto(chain: Vec<String>) -> Result<Self, $crate::ConfigError> {
+                let mut result = Self::default();
+                //This reader doesn't track provenance; a throwaway map keeps apply_optional's signature uniform
+                let mut sources = std::collections::HashMap::new();
+
+                for entry in chain {
+                    for filename in Self::expand_chain_entry(&entry) {
+                        let data = match std::fs::read_to_string(&filename) {
+                            Ok(data) => data,
+                            //Missing files are skipped, same as the other chain readers
+                            Err(_) => continue,
+                        };
+                        let ext = std::path::Path::new(&filename)
+                            .extension()
+                            .and_then(|e| e.to_str())
+                            .map(|e| e.to_ascii_lowercase())
+                            .unwrap_or_default();
+                        let config: $optconfigname = match ext.as_str() {
+                            "toml" => toml::from_str(&data)
+                                .map_err(|e| $crate::ConfigError::Parse(filename.clone(), e.to_string()))?,
+                            #[cfg(feature = "json")]
+                            "json" => serde_json::from_str(&data)
+                                .map_err(|e| $crate::ConfigError::Parse(filename.clone(), e.to_string()))?,
+                            #[cfg(feature = "yaml")]
+                            "yaml" | "yml" => serde_yaml::from_str(&data)
+                                .map_err(|e| $crate::ConfigError::Parse(filename.clone(), e.to_string()))?,
+                            _ => return Err($crate::ConfigError::UnsupportedFormat(filename.clone())),
+                        };
+                        result.apply_optional(config, &filename, &mut sources, false);
+                    }
+                }
+
+                Ok(result)
+            }
+            /// Load this config from `chain` and keep it hot: returns a [`OneConfig`] handle whose `.get()`
+            /// yields a cheap clone of the current value, transparently updated whenever any file in the
+            /// chain changes on disk. A reload that fails to parse keeps the previous value and logs,
+            /// rather than clobbering good config with a parse failure.
+            ///
+            /// Requires the `watch` cargo feature (pulls in the `notify` filesystem watcher). The feature
+            /// check is part of the macro's output, so it is evaluated in the crate that invokes
+            /// `create_config!`: that crate needs its own `watch` feature and `notify` dependency, and this
+            /// crate's `watch` feature must be on for `OneConfig::watch_chain`.
+            #[cfg(feature = "watch")]
+            #[allow(dead_code)]
+            pub fn watch_chain(chain: Vec<String>) -> notify::Result<$crate::OneConfig<Self>> {
+                $crate::OneConfig::watch_chain(chain, |chain| {
+                    let checked = chain.iter()
+                        .map(|p| (p.clone(), $crate::MustRead::TolerateAbsence))
+                        .collect();
+                    match Self::read_chain_checked_toml(checked) {
+                        Ok(config) => Some(config),
                         Err(error) => {
-                            println!("read_chain_json file read error: {}", error.to_string())
+                            println!("watch_chain reload error: {}", error);
+                            None
+                        }
+                    }
+                })
+            }
+            //Read a single toml file and overlay its (optional) values onto ourselves. Missing files and
+            //parse errors are reported but not fatal, matching the silent-skip behavior callers rely on.
+            fn apply_toml_file(&mut self, filename: &str, sources: &mut std::collections::HashMap<&'static str, String>, track: bool) {
+                //Maybe async someday? idk. Also reading into memory? It's just configs so it's fine
+                //but clearly there are much better ways (serde gives from_reader)
+                let data = std::fs::read_to_string(filename);
+                match data {
+                    Ok(data) => {
+                        let config_result: Result<$optconfigname, _> = toml::from_str(&data);
+                        match config_result {
+                            Ok(config) => {
+                                self.apply_optional(config, filename, sources, track);
+                            }
+                            Err(error) => {
+                                println!("read_chain_json json parse error: {}", error.to_string())
+                            }
                         }
                     }
+                    Err(error) => {
+                        println!("read_chain_json file read error: {}", error.to_string())
+                    }
                 }
-
+            }
+            //Expand a single chain entry into the ordered list of toml files it names. A directory expands to
+            //its `*.toml` children (lexicographic order); a regular file expands to itself followed by any
+            //`*.toml` files in a sibling `<basename>.d` directory (the arti.d pattern). Missing paths and
+            //non-toml files simply contribute nothing, so fragments layer over the base deterministically.
+            fn expand_chain_entry(entry: &str) -> Vec<String> {
+                fn dir_tomls(dir: &std::path::Path) -> Vec<String> {
+                    let mut files: Vec<String> = match std::fs::read_dir(dir) {
+                        Ok(readdir) => readdir
+                            .filter_map(|e| e.ok().map(|e| e.path()))
+                            .filter(|p| p.extension().and_then(|s| s.to_str()) == Some("toml"))
+                            .filter_map(|p| p.to_str().map(|s| s.to_string()))
+                            .collect(),
+                        Err(_) => Vec::new(),
+                    };
+                    files.sort();
+                    files
+                }
+                let path = std::path::Path::new(entry);
+                if path.is_dir() {
+                    return dir_tomls(path);
+                }
+                let mut result = vec![entry.to_string()];
+                //Sibling `<basename>.d` directory, e.g. settings.toml -> settings.d
+                let dotd = match entry.strip_suffix(".toml") {
+                    Some(stem) => format!("{}.d", stem),
+                    None => format!("{}.d", entry),
+                };
+                result.extend(dir_tomls(std::path::Path::new(&dotd)));
                 result
             }
             /// The basic case of "I just want to load settings for the given environment". If you give
-            /// (settings, Dev), it will read from the chain "./settings.toml, ./settings.Dev.toml"
+            /// (settings, Dev), it will read from the chain "./settings.toml, ./settings.Dev.toml".
+            /// After the files, values are overlaid from environment variables prefixed with the
+            /// uppercased basename (e.g. `SETTINGS_SOME_INT`); see [`Self::read_chain_with_env_toml`].
             #[allow(dead_code)]
             pub fn read_with_environment_toml(basename: &str, env: Option<&str>) -> Self {
                 Self::read_with_environment_toml_dir(".", basename, env)
             }
             /// Basic read settings from environment within the given directory. If you pass in
-            /// ("configs", "settings", "Dev") it will read from the chain "configs/settings.toml, configs/settings.Dev.toml"
+            /// ("configs", "settings", "Dev") it will read from the chain "configs/settings.toml, configs/settings.Dev.toml".
+            /// Environment variables prefixed with the uppercased basename (e.g. `SETTINGS_SOME_INT`) are
+            /// then overlaid on top, taking precedence over the files.
             pub fn read_with_environment_toml_dir(dir: &str, basename: &str, env: Option<&str>) -> Self {
                 let real_dir = if dir.is_empty() { "." } else { dir };
                 let mut chain = vec![ format!("{}/{}.toml", real_dir, basename) ];
                 if let Some(env) = env {
                     chain.push(format!("{}/{}.{}.toml", real_dir, basename, env));
                 }
-                Self::read_chain_toml(chain)
+                //Uppercase the prefix so the generated lookups (e.g. SETTINGS_SOME_INT) are variables a user
+                //could actually set, rather than the mixed-case "settings_SOME_INT".
+                Self::read_chain_with_env_toml(chain, &basename.to_uppercase())
+            }
+            /// Discover a config chain by walking up the directory tree from the current working directory
+            /// toward the filesystem root, collecting every `<basename>.toml` (and `<basename>.<env>.toml`)
+            /// along the way. The chain is ordered root-most first so that files closer to the cwd override
+            /// ancestors. Finding no files is not an error (you just get defaults).
+            #[allow(dead_code)]
+            pub fn read_with_discovery_toml(basename: &str, env: Option<&str>) -> Self {
+                Self::read_with_discovery_toml_boundary(basename, env, None)
+            }
+            /// Like [`Self::read_with_discovery_toml`], but the upward walk stops after the first directory
+            /// containing `boundary` (e.g. `.git`), so discovery can be pinned to a project root instead of
+            /// climbing all the way to the filesystem root.
+            #[allow(dead_code)]
+            pub fn read_with_discovery_toml_boundary(basename: &str, env: Option<&str>, boundary: Option<&str>) -> Self {
+                let start = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+                Self::read_chain_toml(Self::discovery_chain(&start, basename, env, boundary))
+            }
+            //Build the discovery chain by walking up from `start` toward the root (root-most first so nearer
+            //directories override ancestors). If `boundary` is given, the walk stops after the first directory
+            //that contains a `boundary` entry (inclusive), e.g. the repo root detected via `.git`.
+            fn discovery_chain(start: &std::path::Path, basename: &str, env: Option<&str>, boundary: Option<&str>) -> Vec<String> {
+                let mut dirs: Vec<std::path::PathBuf> = Vec::new();
+                for dir in start.ancestors() {
+                    dirs.push(dir.to_path_buf());
+                    if let Some(marker) = boundary {
+                        if dir.join(marker).exists() { break; }
+                    }
+                }
+                //ancestors() is nearest-first; reverse so root-most files are applied first and nearer ones win
+                dirs.reverse();
+                let mut chain = Vec::new();
+                for dir in dirs {
+                    chain.push(dir.join(format!("{}.toml", basename)).to_string_lossy().into_owned());
+                    if let Some(env) = env {
+                        chain.push(dir.join(format!("{}.{}.toml", basename, env)).to_string_lossy().into_owned());
+                    }
+                }
+                chain
+            }
+            /// Like [`Self::read_chain_toml`], but after the file chain is applied it overlays any values
+            /// found in process environment variables prefixed with `env_prefix` (e.g. `MYAPP_SOME_INT`
+            /// for a field `some_int`). Environment values win over file values, matching the precedence
+            /// users expect from Cargo-style config.
+            pub fn read_chain_with_env_toml(chain: Vec<String>, env_prefix: &str) -> Self {
+                let mut result = Self::read_chain_toml(chain);
+                //This reader doesn't hand provenance back; a throwaway map keeps apply_environment uniform
+                let mut sources = std::collections::HashMap::new();
+                result.apply_environment(env_prefix, &mut sources, false);
+                result
             }
         }
     };
@@ -158,6 +438,186 @@ mod tests {
         assert_eq!(conf.some_vec, vec![String::from("Just one")]);
     }
 
+    #[test]
+    fn test_readchaindir_toml()
+    {
+        //Build a little fragment directory on the fly; filenames chosen so lexicographic order decides overrides
+        let dir = std::env::temp_dir().join("onestop_dtest");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("10-base.toml"), "some_string = \"first\"\nsome_int = 1\n").unwrap();
+        std::fs::write(dir.join("20-over.toml"), "some_int = 2\n").unwrap();
+        std::fs::write(dir.join("ignoreme.txt"), "some_int = 999\n").unwrap();
+
+        let conf = Config::read_chain_dir_toml(dir.to_str().unwrap());
+        assert_eq!(conf.some_string, String::from("first"));
+        //Later file wins, and the non-toml file is skipped
+        assert_eq!(conf.some_int, 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_sources_tracking()
+    {
+        let dir = std::env::temp_dir().join("onestop_srctest");
+        std::fs::create_dir_all(&dir).unwrap();
+        let base = dir.join("base.toml");
+        let over = dir.join("over.toml");
+        std::fs::write(&base, "some_string = \"a\"\nsome_int = 1\n").unwrap();
+        std::fs::write(&over, "some_int = 2\n").unwrap();
+
+        let (conf, sources) = Config::read_chain_tracked_toml(vec![
+            base.to_str().unwrap().to_string(),
+            over.to_str().unwrap().to_string(),
+        ]);
+        //some_string only appears in base, some_int is last set by over
+        assert_eq!(sources.get("some_string"), Some(&base.to_str().unwrap().to_string()));
+        assert_eq!(sources.get("some_int"), Some(&over.to_str().unwrap().to_string()));
+        //some_vec was never set by any file
+        assert_eq!(sources.get("some_vec"), None);
+        //The value itself is loaded just like the plain reader
+        assert_eq!(conf.some_int, 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_readchainchecked_optional_missing_ok()
+    {
+        //A missing optional source is skipped, returning defaults
+        let conf = Config::read_chain_checked_toml(vec![
+            (String::from("./does/not/exist.toml"), crate::MustRead::TolerateAbsence),
+        ]).unwrap();
+        assert_eq!(conf.some_int, i32::default());
+    }
+
+    #[test]
+    fn test_readchainchecked_required_missing_errors()
+    {
+        let result = Config::read_chain_checked_toml(vec![
+            (String::from("./does/not/exist.toml"), crate::MustRead::MustRead),
+        ]);
+        assert!(matches!(result, Err(crate::ConfigError::FileNotFound(_))));
+    }
+
+    #[test]
+    fn test_readchainchecked_required_empty_dir_errors()
+    {
+        //A required entry that exists but expands to no toml files (empty directory) must still error,
+        //rather than being silently satisfied because the overlay loop never ran
+        let dir = std::env::temp_dir().join("onestop_emptydir");
+        std::fs::create_dir_all(&dir).unwrap();
+        let result = Config::read_chain_checked_toml(vec![
+            (dir.to_str().unwrap().to_string(), crate::MustRead::MustRead),
+        ]);
+        assert!(matches!(result, Err(crate::ConfigError::FileNotFound(_))));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_readchainchecked_parse_error_errors()
+    {
+        let dir = std::env::temp_dir().join("onestop_checktest");
+        std::fs::create_dir_all(&dir).unwrap();
+        let bad = dir.join("bad.toml");
+        std::fs::write(&bad, "this is = = not toml\n").unwrap();
+        //Even an otherwise-optional source must surface a parse error when it is present
+        let result = Config::read_chain_checked_toml(vec![
+            (bad.to_str().unwrap().to_string(), crate::MustRead::TolerateAbsence),
+        ]);
+        assert!(matches!(result, Err(crate::ConfigError::TomlParse(_, _))));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_readchainauto_mixed()
+    {
+        let dir = std::env::temp_dir().join("onestop_autotest");
+        std::fs::create_dir_all(&dir).unwrap();
+        let toml_file = dir.join("settings.toml");
+        let json_file = dir.join("settings.Prod.json");
+        std::fs::write(&toml_file, "some_string = \"base\"\nsome_int = 1\n").unwrap();
+        std::fs::write(&json_file, "{ \"some_int\": 2 }").unwrap();
+
+        let conf = Config::read_chain_auto(vec![
+            toml_file.to_str().unwrap().to_string(),
+            json_file.to_str().unwrap().to_string(),
+        ]).unwrap();
+        assert_eq!(conf.some_string, String::from("base"));
+        //The later json file overrides the toml value
+        assert_eq!(conf.some_int, 2);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_readchainauto_unknown_extension_errors()
+    {
+        let dir = std::env::temp_dir().join("onestop_autoext");
+        std::fs::create_dir_all(&dir).unwrap();
+        let bad = dir.join("settings.ini");
+        std::fs::write(&bad, "some_int = 5\n").unwrap();
+        let result = Config::read_chain_auto(vec![bad.to_str().unwrap().to_string()]);
+        assert!(matches!(result, Err(crate::ConfigError::UnsupportedFormat(_))));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_discovery_boundary_and_override()
+    {
+        //Lay out a fake project: root has a .git marker and a settings.toml, a subdir overrides it
+        let root = std::env::temp_dir().join("onestop_disc");
+        let sub = root.join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::write(root.join("settings.toml"), "some_string = \"root\"\nsome_int = 1\n").unwrap();
+        std::fs::write(sub.join("settings.toml"), "some_int = 2\n").unwrap();
+
+        let chain = Config::discovery_chain(&sub, "settings", None, Some(".git"));
+        //Root-most first so the nearer (sub) file overrides
+        assert_eq!(chain.len(), 2);
+        assert!(chain[0].ends_with("onestop_disc/settings.toml"));
+        assert!(chain[1].ends_with("sub/settings.toml"));
+
+        let conf = Config::read_chain_toml(chain);
+        assert_eq!(conf.some_string, String::from("root"));
+        assert_eq!(conf.some_int, 2);
+
+        std::fs::remove_dir_all(&root).ok();
+    }
+
+    #[test]
+    fn test_readchainwithenv()
+    {
+        //Use a prefix nothing else touches so parallel tests don't stomp on these vars
+        std::env::set_var("OSTESTENV_SOME_STRING", "from env");
+        std::env::set_var("OSTESTENV_SOME_INT", "99");
+        std::env::set_var("OSTESTENV_SOME_VEC", "one,two,three");
+        let conf = Config::read_chain_with_env_toml(
+            vec![format!("{}/{}", SETTINGSDIR, SETTINGSBASE)], "OSTESTENV");
+        //Env values override what the file set
+        assert_eq!(conf.some_string, String::from("from env"));
+        assert_eq!(conf.some_int, 99);
+        assert_eq!(conf.some_vec, vec![String::from("one"), String::from("two"), String::from("three")]);
+    }
+
+    #[test]
+    fn test_readchainwithenv_string_not_coerced()
+    {
+        //A String field whose env value looks numeric or contains a comma must stay a string, not be
+        //coerced to an int or split into an array.
+        std::env::set_var("OSTESTENV2_SOME_STRING", "12345");
+        std::env::set_var("OSTESTENV2_SOME_VEC", "a,b");
+        let conf = Config::read_chain_with_env_toml(Vec::new(), "OSTESTENV2");
+        assert_eq!(conf.some_string, String::from("12345"));
+        assert_eq!(conf.some_vec, vec![String::from("a"), String::from("b")]);
+
+        std::env::set_var("OSTESTENV2_SOME_STRING", "x, y");
+        let conf = Config::read_chain_with_env_toml(Vec::new(), "OSTESTENV2");
+        assert_eq!(conf.some_string, String::from("x, y"));
+    }
+
     #[test]
     fn test_readwithenvironmenttoml_production()
     {